@@ -1,5 +1,101 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use chrono::*;
+use clap::Parser;
+
+/// CLI flags for tuning the Pomodoro rhythm. Anything left unset here falls
+/// back to the matching environment variable (see [`Config::load`]).
+#[derive(Clone, Debug, Default, Parser)]
+#[command(about = "A small Pomodoro timer")]
+struct CliArgs {
+    /// Length of a work interval, in minutes. Must be at least 1.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    work: Option<u32>,
+
+    /// Length of a short break, in minutes. Must be at least 1.
+    #[arg(long = "short-break", value_parser = clap::value_parser!(u32).range(1..))]
+    short_break: Option<u32>,
+
+    /// Length of the long break, in minutes. Must be at least 1.
+    #[arg(long = "long-break", value_parser = clap::value_parser!(u32).range(1..))]
+    long_break: Option<u32>,
+
+    /// Number of work intervals per long break. Must be at least 1.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    intervals: Option<u32>,
+
+    /// Path to a custom WAV file to play when an interval finishes. Defaults
+    /// to the bundled chime.
+    #[arg(long)]
+    sound: Option<std::path::PathBuf>,
+}
+
+/// Resolved Pomodoro timing configuration: how long each phase lasts and how
+/// many work intervals happen before the long break.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub work: u32,
+    pub short_break: u32,
+    pub long_break: u32,
+    pub intervals: u32,
+    pub sound: Option<std::path::PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            work: 25,
+            short_break: 5,
+            long_break: 15,
+            intervals: 4,
+            sound: None,
+        }
+    }
+}
+
+/// Environment-variable overrides (`WORK`, `SHORT_BREAK`, `LONG_BREAK`,
+/// `INTERVALS`, `SOUND`). Every field is optional so an unset variable can be
+/// told apart from one explicitly set to the same value as a default.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+struct EnvOverrides {
+    work: Option<u32>,
+    short_break: Option<u32>,
+    long_break: Option<u32>,
+    intervals: Option<u32>,
+    sound: Option<std::path::PathBuf>,
+}
+
+impl Config {
+    /// Resolves the effective config from, in order of precedence:
+    /// 1. CLI flags (`--work`, `--short-break`, `--long-break`, `--intervals`, `--sound`)
+    /// 2. Environment variables (`WORK`, `SHORT_BREAK`, `LONG_BREAK`, `INTERVALS`, `SOUND`)
+    /// 3. `persisted`, the config saved from the previous run (or the demo
+    ///    defaults of 25/5/15 minutes, 4 intervals, bundled chime on first run)
+    pub fn load(persisted: Config) -> Self {
+        let cli = CliArgs::parse();
+        let env: EnvOverrides = envy::from_env().unwrap_or_default();
+        Self::resolve(&cli, &env, persisted)
+    }
+
+    /// The precedence logic behind [`Config::load`], split out so it can be
+    /// exercised without touching real argv/env state.
+    ///
+    /// Durations and the interval count are clamped to at least 1: a 0-minute
+    /// duration would leave the timer stuck forever at `0:0:0` (it can never
+    /// register as expired), and 0 intervals would divide by zero when
+    /// displaying "interval N of 0". `CliArgs` already rejects 0 at parse
+    /// time, but env vars and a persisted config from an older version don't
+    /// go through that check.
+    fn resolve(cli: &CliArgs, env: &EnvOverrides, persisted: Config) -> Self {
+        Config {
+            work: cli.work.or(env.work).unwrap_or(persisted.work).max(1),
+            short_break: cli.short_break.or(env.short_break).unwrap_or(persisted.short_break).max(1),
+            long_break: cli.long_break.or(env.long_break).unwrap_or(persisted.long_break).max(1),
+            intervals: cli.intervals.or(env.intervals).unwrap_or(persisted.intervals).max(1),
+            sound: cli.sound.clone().or_else(|| env.sound.clone()).or(persisted.sound),
+        }
+    }
+}
 
 /// How often we repaint the demo app by default
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -42,6 +138,9 @@ impl Default for RunMode {
     }
 }
 
+/// How often the "stand up and stretch" nudge re-fires during a work interval.
+const STRETCH_INTERVAL: Duration = Duration::from_secs(20 * 60);
+
 pub struct Timer {
     is_running: bool,
     previous_timestamp: Instant,
@@ -61,15 +160,31 @@ impl Timer {
         }
     }
 
-    fn update(&mut self)
-    {
+    /// Advances the countdown. Returns `true` the moment the timer transitions
+    /// from running to expired, so the caller can react exactly once (e.g. play
+    /// a sound) instead of every frame the display reads `0:0:0`.
+    ///
+    /// Saturates at zero instead of subtracting past it: in reactive mode a
+    /// frame can land long after the timer should have hit zero (e.g. after
+    /// the app sat idle), and `Instant::elapsed()` is always an accurate
+    /// measurement of how much real time actually passed, so there's no
+    /// reason to distrust or clamp it.
+    fn update(&mut self) -> bool {
         if !self.is_running {
-            return;
+            return false;
         }
 
-        let ms_difference = self.previous_timestamp.elapsed().as_millis(); 
-        self.timer -= ms_difference;
+        let elapsed = self.previous_timestamp.elapsed();
         self.previous_timestamp = Instant::now();
+        let ms_elapsed = elapsed.as_millis();
+
+        let just_expired = self.timer > 0 && ms_elapsed >= self.timer;
+        self.timer = self.timer.saturating_sub(ms_elapsed);
+        if just_expired {
+            self.is_running = false;
+        }
+
+        just_expired
     }
 
     fn start_timer(&mut self){
@@ -86,6 +201,14 @@ impl Timer {
         self.timer = self.start_time;
     }
 
+    /// Milliseconds left on the countdown. Unlike wall-clock time this only
+    /// advances while the timer is actually running, so it's the right basis
+    /// for scheduling a reminder relative to how much of the interval is left
+    /// rather than how long ago it was started.
+    fn remaining_ms(&self) -> u128 {
+        self.timer
+    }
+
     fn get_hours_remaining(&self) -> u32 {
         let hours = (self.timer / 1000 / 60 / 60) % 60;
         hours as u32
@@ -100,6 +223,255 @@ impl Timer {
         let seconds = (self.timer / 1000) % 60;
         seconds as u32
     }
+
+    /// How long until the displayed digits change, i.e. until the next whole second
+    /// boundary is crossed. Used to schedule a precise repaint in reactive mode instead
+    /// of polling every frame.
+    fn ms_to_next_tick(&self) -> Duration {
+        let remainder = self.timer % 1000;
+        let ms = if remainder == 0 { 1000 } else { 1000 - remainder };
+        Duration::from_millis(ms as u64)
+    }
+
+}
+
+/// Identifies a deadline registered with a [`Scheduler`]. Cheap to copy and
+/// store; passing the same token back into [`Scheduler::start`] restarts that
+/// task without disturbing any other scheduled task.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+struct TimerToken(u32);
+
+/// Tracks deadlines for any number of independently restartable scheduled
+/// tasks - e.g. "phase ends", "halfway warning", "stretch nudge" - so the app
+/// isn't limited to a single one-shot countdown at a time.
+#[derive(Default)]
+struct Scheduler {
+    next_token: u32,
+    deadlines: Vec<(TimerToken, Instant)>,
+}
+
+impl Scheduler {
+    /// Registers `duration` from now as the deadline for `token`, or acquires
+    /// a fresh token if `token` is `None`. Passing back a token you already
+    /// hold restarts its deadline in place.
+    fn start(&mut self, token: Option<TimerToken>, duration: Duration) -> TimerToken {
+        let token = token.unwrap_or_else(|| {
+            let token = TimerToken(self.next_token);
+            self.next_token += 1;
+            token
+        });
+
+        let deadline = Instant::now() + duration;
+        match self.deadlines.iter_mut().find(|(t, _)| *t == token) {
+            Some(entry) => entry.1 = deadline,
+            None => self.deadlines.push((token, deadline)),
+        }
+
+        token
+    }
+
+    /// Clears `token`'s deadline. Every other scheduled task is left running.
+    fn stop(&mut self, token: TimerToken) {
+        self.deadlines.retain(|(t, _)| *t != token);
+    }
+
+    /// Whether `token`'s deadline has passed. A token with no registered
+    /// deadline (never started, or already stopped) is never expired.
+    fn is_expired(&self, token: TimerToken, now: Instant) -> bool {
+        self.deadlines
+            .iter()
+            .any(|(t, deadline)| *t == token && now >= *deadline)
+    }
+
+    /// The soonest upcoming deadline across every scheduled task, if any.
+    /// Used to drive the reactive repaint wake time alongside the display
+    /// timer's own [`Timer::ms_to_next_tick`].
+    fn soonest_deadline(&self) -> Option<Instant> {
+        self.deadlines.iter().map(|(_, deadline)| *deadline).min()
+    }
+}
+
+/// The three phases of a Pomodoro cycle: a work interval, the short break that
+/// follows most work intervals, and the long break that follows every fourth one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Work => "Work",
+            Phase::ShortBreak => "Short Break",
+            Phase::LongBreak => "Long Break",
+        }
+    }
+}
+
+/// Drives the real Pomodoro technique: four work intervals each followed by a
+/// short break, then a long break after the fourth, looping automatically.
+pub struct PomodoroSession {
+    phase: Phase,
+    completed_work_intervals: u32,
+    timer: Timer,
+    config: Config,
+    auto_start: bool,
+}
+
+impl PomodoroSession {
+    fn new(config: Config) -> Self {
+        Self {
+            phase: Phase::Work,
+            completed_work_intervals: 0,
+            timer: Timer::new(0, config.work, 0),
+            config,
+            auto_start: false,
+        }
+    }
+
+    fn timer_for(&self, phase: Phase) -> Timer {
+        match phase {
+            Phase::Work => Timer::new(0, self.config.work, 0),
+            Phase::ShortBreak => Timer::new(0, self.config.short_break, 0),
+            Phase::LongBreak => Timer::new(0, self.config.long_break, 0),
+        }
+    }
+
+    /// The configured number of work intervals before a long break, clamped
+    /// to at least 1 so a malformed config (e.g. a hand-edited save file)
+    /// can't divide by zero.
+    fn intervals(&self) -> u32 {
+        self.config.intervals.max(1)
+    }
+
+    /// "interval N of `intervals`" for the current (or just-completed) work interval.
+    fn interval_display(&self) -> u32 {
+        (self.completed_work_intervals % self.intervals()) + 1
+    }
+
+    /// The configured length of the current phase, used together with the
+    /// timer's remaining time to find the halfway point of the interval.
+    fn phase_duration(&self) -> Duration {
+        let minutes = match self.phase {
+            Phase::Work => self.config.work,
+            Phase::ShortBreak => self.config.short_break,
+            Phase::LongBreak => self.config.long_break,
+        };
+        Duration::from_secs(minutes as u64 * 60)
+    }
+
+    /// Moves to the next phase, builds a fresh timer for it, and starts it if
+    /// `auto_start` is set. Called once the active timer reaches zero.
+    fn advance(&mut self) {
+        self.phase = match self.phase {
+            Phase::Work => {
+                self.completed_work_intervals += 1;
+                if self.completed_work_intervals % self.intervals() == 0 {
+                    Phase::LongBreak
+                } else {
+                    Phase::ShortBreak
+                }
+            }
+            Phase::ShortBreak => Phase::Work,
+            Phase::LongBreak => {
+                self.completed_work_intervals = 0;
+                Phase::Work
+            }
+        };
+
+        self.timer = self.timer_for(self.phase);
+        if self.auto_start {
+            self.timer.start_timer();
+        }
+    }
+
+    /// Checks the active timer and advances the session if it just expired.
+    /// Returns whether a transition happened, so the caller can e.g. play a sound.
+    fn update(&mut self) -> bool {
+        if self.timer.update() {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn start(&mut self) {
+        self.auto_start = true;
+        self.timer.start_timer();
+    }
+
+    fn skip(&mut self) {
+        self.advance();
+    }
+
+    fn reset(&mut self) {
+        self.auto_start = false;
+        self.phase = Phase::Work;
+        self.completed_work_intervals = 0;
+        self.timer = self.timer_for(Phase::Work);
+    }
+}
+
+impl Default for PomodoroSession {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+/// The bundled chime played when a timer expires, unless `--sound` points to
+/// a different WAV file.
+const DEFAULT_CHIME: &[u8] = include_bytes!("../assets/chime.wav");
+
+/// Plays a chime when a timer expires. Holds the rodio output stream alive
+/// for as long as the app runs; playback is skipped (but not an error) if no
+/// audio device is available.
+struct Sound {
+    _stream: Option<rodio::OutputStream>,
+    handle: Option<rodio::OutputStreamHandle>,
+    chime: Vec<u8>,
+}
+
+impl Sound {
+    fn new(path: Option<&std::path::Path>) -> Self {
+        let chime = path
+            .and_then(|path| std::fs::read(path).ok())
+            .unwrap_or_else(|| DEFAULT_CHIME.to_vec());
+
+        match rodio::OutputStream::try_default() {
+            Ok((stream, handle)) => Self {
+                _stream: Some(stream),
+                handle: Some(handle),
+                chime,
+            },
+            Err(_) => Self {
+                _stream: None,
+                handle: None,
+                chime,
+            },
+        }
+    }
+
+    fn play(&self) {
+        let Some(handle) = &self.handle else {
+            return;
+        };
+
+        if let Ok(sink) = rodio::Sink::try_new(handle) {
+            if let Ok(source) = rodio::Decoder::new(std::io::Cursor::new(self.chime.clone())) {
+                sink.append(source);
+                sink.detach();
+            }
+        }
+    }
+}
+
+impl Default for Sound {
+    fn default() -> Self {
+        Self::new(None)
+    }
 }
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -113,39 +485,123 @@ pub struct TemplateApp {
     #[serde(skip)]
     run_mode: RunMode,
 
+    // Persisted so the app remembers the user's chosen interval lengths across
+    // restarts even if they don't pass the CLI flags / env vars again.
+    config: Config,
+
+    #[serde(skip)]
+    session: PomodoroSession,
+
+    #[serde(skip)]
+    sound: Sound,
+
+    muted: bool,
+
+    // Drives the halfway and stretch reminders alongside the main countdown.
     #[serde(skip)]
-    active_timer_index: usize,
+    scheduler: Scheduler,
 
     #[serde(skip)]
-    timers : Vec<Timer>,
+    halfway_reminder: Option<TimerToken>,
+
+    #[serde(skip)]
+    stretch_reminder: Option<TimerToken>,
+
+    #[serde(skip)]
+    halfway_reminder_due: bool,
+
+    #[serde(skip)]
+    stretch_reminder_due: bool,
 }
 
 impl Default for TemplateApp {
     fn default() -> Self {
-        let pomodoro_timer = Timer::new(2, 23, 17);
-        let pause_timer = Timer::new(0, 15, 30);
+        let config = Config::default();
         Self {
             value: 2.7,
-            run_mode: RunMode::Continuous,
-            active_timer_index: 0,
-            timers: vec![pomodoro_timer, pause_timer]
+            run_mode: RunMode::Reactive,
+            session: PomodoroSession::new(config.clone()),
+            sound: Sound::new(config.sound.as_deref()),
+            muted: false,
+            scheduler: Scheduler::default(),
+            halfway_reminder: None,
+            stretch_reminder: None,
+            halfway_reminder_due: false,
+            stretch_reminder_due: false,
+            config,
         }
     }
 }
 
 impl TemplateApp {
-    /// Called once before the first frame.
+    /// Called once before the first frame. Resolves the effective [`Config`]
+    /// from CLI flags / env vars layered on top of whatever was persisted
+    /// from a previous session (see [`Config::load`]), so a bare launch keeps
+    /// remembering the user's chosen interval lengths instead of reverting to
+    /// the demo defaults.
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         // This is also where you can customize the look and feel of egui using
         // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+        let mut app: Self = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            .unwrap_or_default();
+
+        let config = Config::load(app.config.clone());
+        app.sound = Sound::new(config.sound.as_deref());
+        app.session = PomodoroSession::new(config.clone());
+        app.config = config;
+        app
+    }
+}
+
+/// (Re)schedules the halfway and stretch reminders to match the active timer,
+/// or clears both when nothing is running. Called whenever the session
+/// starts, stops, skips, or auto-advances to a new phase.
+///
+/// The halfway reminder is rearmed from the timer's *remaining* time rather
+/// than the phase's full duration, so pausing and resuming doesn't push it
+/// later: `remaining_ms` only ticks down while the timer actually runs, so
+/// "half the phase left" always lands at the same real point in the
+/// countdown no matter how many times it was paused along the way. Once
+/// that point has passed, it's left cleared instead of re-armed.
+fn sync_reminders(
+    session: &PomodoroSession,
+    scheduler: &mut Scheduler,
+    halfway_reminder: &mut Option<TimerToken>,
+    stretch_reminder: &mut Option<TimerToken>,
+    halfway_reminder_due: &mut bool,
+    stretch_reminder_due: &mut bool,
+) {
+    *halfway_reminder_due = false;
+    *stretch_reminder_due = false;
+
+    if !session.timer.is_running {
+        if let Some(token) = halfway_reminder.take() {
+            scheduler.stop(token);
         }
+        if let Some(token) = stretch_reminder.take() {
+            scheduler.stop(token);
+        }
+        return;
+    }
 
-        Default::default()
+    let half_ms = session.phase_duration().as_millis() / 2;
+    let remaining_ms = session.timer.remaining_ms();
+    if remaining_ms > half_ms {
+        let until_halfway = Duration::from_millis((remaining_ms - half_ms) as u64);
+        *halfway_reminder = Some(scheduler.start(*halfway_reminder, until_halfway));
+    } else if let Some(token) = halfway_reminder.take() {
+        scheduler.stop(token);
+    }
+
+    if session.phase == Phase::Work {
+        *stretch_reminder = Some(scheduler.start(*stretch_reminder, STRETCH_INTERVAL));
+    } else if let Some(token) = stretch_reminder.take() {
+        scheduler.stop(token);
     }
 }
 
@@ -160,56 +616,109 @@ impl eframe::App for TemplateApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let value = &mut self.value;
         let run_mode = self.run_mode;
-        let active_timer_index = &mut self.active_timer_index;
-        let timers = &mut self.timers;
+        let session = &mut self.session;
+        let muted = &mut self.muted;
+        let sound = &self.sound;
+        let scheduler = &mut self.scheduler;
+        let halfway_reminder = &mut self.halfway_reminder;
+        let stretch_reminder = &mut self.stretch_reminder;
+        let halfway_reminder_due = &mut self.halfway_reminder_due;
+        let stretch_reminder_due = &mut self.stretch_reminder_due;
+        let mut next_repaint = None;
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Side Panel");
+            ui.heading(format!(
+                "{} - interval {} of {}",
+                session.phase.label(),
+                session.interval_display(),
+                session.intervals()
+            ));
 
             ui.add(egui::Slider::new(value, 0.0..=10.0).text("value"));
             if ui.button("Increment").clicked() {
                 *value += 1.0;
             }
 
-            let mut timer_switched = false;
-            ui.horizontal(|ui|{
-                if ui.button("Pomodoro").clicked() {
-                    *active_timer_index = 0;
-                    timer_switched = true;
+            ui.label(format!(
+                "{}:{}:{}",
+                session.timer.get_hours_remaining(),
+                session.timer.get_minutes_remaining(),
+                session.timer.get_seconds_remaining()
+            ));
+
+            ui.horizontal(|ui| {
+                if session.timer.is_running {
+                    if ui.button("pause").clicked() {
+                        session.timer.pause_timer();
+                        sync_reminders(session, scheduler, halfway_reminder, stretch_reminder, halfway_reminder_due, stretch_reminder_due);
+                    }
+                } else if ui.button("start").clicked() {
+                    session.start();
+                    sync_reminders(session, scheduler, halfway_reminder, stretch_reminder, halfway_reminder_due, stretch_reminder_due);
                 }
-                if ui.button("Pause").clicked() {
-                    *active_timer_index = 1;
-                    timer_switched = true;
+
+                if ui.button("skip").clicked() {
+                    session.skip();
+                    sync_reminders(session, scheduler, halfway_reminder, stretch_reminder, halfway_reminder_due, stretch_reminder_due);
                 }
-            });
 
-            let active_timer = &mut timers[*active_timer_index];
+                if ui.button("reset session").clicked() {
+                    session.reset();
+                    sync_reminders(session, scheduler, halfway_reminder, stretch_reminder, halfway_reminder_due, stretch_reminder_due);
+                }
 
-            if timer_switched {
-                active_timer.stop_timer();
+                ui.checkbox(muted, "mute");
+            });
+
+            let transitioned = session.update();
+            if transitioned {
+                sync_reminders(session, scheduler, halfway_reminder, stretch_reminder, halfway_reminder_due, stretch_reminder_due);
+            }
+            if transitioned && !*muted {
+                sound.play();
             }
 
-            //ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
-                ui.label(format!("{}:{}:{}", active_timer.get_hours_remaining(), active_timer.get_minutes_remaining(), active_timer.get_seconds_remaining()));
-            //});
+            let now = Instant::now();
+            if let Some(token) = *halfway_reminder {
+                if scheduler.is_expired(token, now) {
+                    *halfway_reminder_due = true;
+                    scheduler.stop(token);
+                    *halfway_reminder = None;
+                }
+            }
+            if let Some(token) = *stretch_reminder {
+                if scheduler.is_expired(token, now) {
+                    *stretch_reminder_due = true;
+                    *stretch_reminder = Some(scheduler.start(Some(token), STRETCH_INTERVAL));
+                }
+            }
 
-            ui.horizontal(|ui| {
-                if active_timer.is_running {
-                    if ui.button("pause timer").clicked() {
-                        active_timer.pause_timer();
+            if *halfway_reminder_due {
+                ui.horizontal(|ui| {
+                    ui.label("Halfway through this interval.");
+                    if ui.button("dismiss").clicked() {
+                        *halfway_reminder_due = false;
                     }
-                }else {
-                    if ui.button("start timer").clicked() {
-                        active_timer.start_timer();
+                });
+            }
+
+            if *stretch_reminder_due {
+                ui.horizontal(|ui| {
+                    ui.label("Time to stand up and stretch.");
+                    if ui.button("dismiss").clicked() {
+                        *stretch_reminder_due = false;
                     }
-                }
-            
-                if ui.button("stop timer").clicked() {
-                    active_timer.stop_timer();
-                }
-            });
+                });
+            }
 
-            active_timer.update();
+            if session.timer.is_running {
+                next_repaint = Some(session.timer.ms_to_next_tick());
+            }
+
+            if let Some(deadline) = scheduler.soonest_deadline() {
+                let scheduler_wait = deadline.saturating_duration_since(Instant::now());
+                next_repaint = Some(next_repaint.map_or(scheduler_wait, |d: Duration| d.min(scheduler_wait)));
+            }
 
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
                 ui.horizontal(|ui| {
@@ -227,11 +736,167 @@ impl eframe::App for TemplateApp {
             });
         });
 
-        if run_mode == RunMode::Continuous {
-            // Tell the backend to repaint as soon as possible
-            ctx.request_repaint();
+        match (run_mode, next_repaint) {
+            (RunMode::Continuous, _) => {
+                // Tell the backend to repaint as soon as possible
+                ctx.request_repaint();
+            }
+            (RunMode::Reactive, Some(duration)) => {
+                // Wake up exactly when the displayed digits need to change instead of
+                // polling every frame.
+                ctx.request_repaint_after(duration);
+            }
+            (RunMode::Reactive, None) => {
+                // Timer is paused/stopped: nothing to animate, so idle at zero CPU.
+            }
         }
     }
 
-    
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_cycles_through_a_full_pomodoro() {
+        let mut session = PomodoroSession::new(Config::default());
+        assert_eq!(session.phase, Phase::Work);
+        assert_eq!(session.completed_work_intervals, 0);
+
+        // Work -> short break, three times in a row.
+        for completed in 1..session.config.intervals {
+            session.advance();
+            assert_eq!(session.phase, Phase::ShortBreak);
+            assert_eq!(session.completed_work_intervals, completed);
+
+            session.advance();
+            assert_eq!(session.phase, Phase::Work);
+        }
+
+        // The 4th work interval goes to a long break instead of a short one.
+        session.advance();
+        assert_eq!(session.phase, Phase::LongBreak);
+        assert_eq!(session.completed_work_intervals, 4);
+
+        // The long break loops back to Work with the counter reset.
+        session.advance();
+        assert_eq!(session.phase, Phase::Work);
+        assert_eq!(session.completed_work_intervals, 0);
+    }
+
+    #[test]
+    fn zero_intervals_does_not_panic() {
+        let config = Config {
+            intervals: 0,
+            ..Config::default()
+        };
+        let mut session = PomodoroSession::new(config);
+
+        // Would divide by zero without the `.max(1)` guard in `intervals()`.
+        assert_eq!(session.interval_display(), 1);
+        session.advance();
+        assert_eq!(session.phase, Phase::ShortBreak);
+    }
+
+    #[test]
+    fn config_resolve_prefers_cli_over_env_over_persisted() {
+        let persisted = Config {
+            work: 10,
+            short_break: 2,
+            long_break: 20,
+            intervals: 2,
+            sound: None,
+        };
+
+        let cli = CliArgs {
+            work: Some(50),
+            ..Default::default()
+        };
+        let env = EnvOverrides {
+            work: Some(99),
+            short_break: Some(8),
+            ..Default::default()
+        };
+
+        let resolved = Config::resolve(&cli, &env, persisted);
+
+        assert_eq!(resolved.work, 50); // CLI wins over env and persisted
+        assert_eq!(resolved.short_break, 8); // env wins over persisted
+        assert_eq!(resolved.long_break, 20); // falls back to persisted
+        assert_eq!(resolved.intervals, 2); // falls back to persisted
+    }
+
+    #[test]
+    fn config_resolve_falls_back_to_defaults_with_nothing_persisted() {
+        let resolved = Config::resolve(&CliArgs::default(), &EnvOverrides::default(), Config::default());
+        assert_eq!(resolved.work, Config::default().work);
+        assert_eq!(resolved.intervals, Config::default().intervals);
+    }
+
+    #[test]
+    fn sync_reminders_reschedules_from_remaining_time_not_wall_clock() {
+        let mut session = PomodoroSession::new(Config::default());
+        session.start();
+        let mut scheduler = Scheduler::default();
+        let mut halfway_reminder = None;
+        let mut stretch_reminder = None;
+        let mut halfway_reminder_due = false;
+        let mut stretch_reminder_due = false;
+
+        sync_reminders(&session, &mut scheduler, &mut halfway_reminder, &mut stretch_reminder, &mut halfway_reminder_due, &mut stretch_reminder_due);
+        let deadline_before_pause = scheduler.deadlines.iter().find(|(t, _)| Some(*t) == halfway_reminder).unwrap().1;
+
+        // Simulate a pause/resume: the timer's remaining time doesn't change,
+        // so re-syncing (as happens on every pause and resume) must land on
+        // the same deadline instead of pushing it later from "now".
+        session.timer.pause_timer();
+        sync_reminders(&session, &mut scheduler, &mut halfway_reminder, &mut stretch_reminder, &mut halfway_reminder_due, &mut stretch_reminder_due);
+        session.timer.start_timer();
+        sync_reminders(&session, &mut scheduler, &mut halfway_reminder, &mut stretch_reminder, &mut halfway_reminder_due, &mut stretch_reminder_due);
+
+        let deadline_after_resume = scheduler.deadlines.iter().find(|(t, _)| Some(*t) == halfway_reminder).unwrap().1;
+        let drift = deadline_after_resume.saturating_duration_since(deadline_before_pause)
+            + deadline_before_pause.saturating_duration_since(deadline_after_resume);
+        assert!(drift < Duration::from_millis(50), "pause/resume shifted the halfway deadline by {drift:?}");
+    }
+
+    #[test]
+    fn sync_reminders_does_not_rearm_halfway_reminder_past_the_midpoint() {
+        let mut session = PomodoroSession::new(Config::default());
+        session.start();
+        // Fast-forward past the halfway point of the work interval.
+        session.timer.timer = session.phase_duration().as_millis() / 2 - 1;
+
+        let mut scheduler = Scheduler::default();
+        let mut halfway_reminder = None;
+        let mut stretch_reminder = None;
+        let mut halfway_reminder_due = false;
+        let mut stretch_reminder_due = false;
+        sync_reminders(&session, &mut scheduler, &mut halfway_reminder, &mut stretch_reminder, &mut halfway_reminder_due, &mut stretch_reminder_due);
+
+        assert_eq!(halfway_reminder, None);
+    }
+
+    #[test]
+    fn config_resolve_clamps_zero_to_one() {
+        let env = EnvOverrides {
+            work: Some(0),
+            intervals: Some(0),
+            ..Default::default()
+        };
+        let persisted = Config {
+            short_break: 0,
+            long_break: 0,
+            ..Config::default()
+        };
+
+        let resolved = Config::resolve(&CliArgs::default(), &env, persisted);
+
+        assert_eq!(resolved.work, 1);
+        assert_eq!(resolved.short_break, 1);
+        assert_eq!(resolved.long_break, 1);
+        assert_eq!(resolved.intervals, 1);
+    }
 }
\ No newline at end of file